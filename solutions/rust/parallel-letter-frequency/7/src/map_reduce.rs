@@ -0,0 +1,32 @@
+use std::{sync::{mpsc, Arc}, thread};
+
+use crate::chunking::chunk_bounds;
+
+/// Splits `input` across `worker_count` threads, runs `map` on each
+/// worker's slice to produce a partial result, then folds all partials
+/// together with `reduce` on the calling thread.
+///
+/// `reduce` is seeded with `U::default()`, so it only needs to know how
+/// to combine two partials, not how to build the first one.
+pub fn map_reduce<U, M, R>(input: &[&str], worker_count: usize, map: M, reduce: R) -> U
+where
+    U: Default + Send,
+    M: Fn(&[&str]) -> U + Sync,
+    R: Fn(U, U) -> U,
+{
+    thread::scope(|scope| {
+        let input = Arc::new(input);
+        let (tx, rx) = mpsc::channel();
+        let map = &map;
+        let _ = chunk_bounds(input.len(), worker_count).into_iter().map(|(chunk_start, chunk_end)| {
+            let input = Arc::clone(&input);
+            let tx = tx.clone();
+            scope.spawn(move || {
+                tx.send(map(&input[chunk_start..chunk_end])).unwrap();
+            });
+        }).collect::<Vec<_>>();
+        drop(tx);
+
+        rx.into_iter().fold(U::default(), &reduce)
+    })
+}
@@ -0,0 +1,57 @@
+use std::{
+    collections::HashMap,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::RwLock,
+};
+
+/// A hash map split into `S` independently-locked shards, so concurrent
+/// writers touching different keys rarely contend with each other.
+///
+/// Each worker takes a write lock only on the shard its key hashes into,
+/// increments in place, and the result is assembled by draining every
+/// shard once at the end — there is no final O(workers * keys) merge.
+pub struct ShardedMap<K> {
+    shards: Vec<RwLock<HashMap<K, usize>>>,
+}
+
+impl<K: Eq + Hash + Clone> ShardedMap<K> {
+    /// `shard_count` is clamped to at least 1, so `0` degrades to a
+    /// single shard instead of making every `shard_index` lookup divide
+    /// by zero.
+    pub fn new(shard_count: usize) -> Self {
+        let shard_count = shard_count.max(1);
+        Self {
+            shards: (0..shard_count).map(|_| RwLock::new(HashMap::new())).collect(),
+        }
+    }
+
+    pub fn increment(&self, key: K) {
+        let shard = &self.shards[self.shard_index(&key)];
+        *shard.write().unwrap().entry(key).or_insert(0) += 1;
+    }
+
+    fn shard_index(&self, key: &K) -> usize {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        (hasher.finish() as usize) % self.shards.len()
+    }
+
+    /// Drains every shard into a single map. Consumes `self` since the
+    /// shards are emptied in the process.
+    pub fn into_map(self) -> HashMap<K, usize> {
+        self.shards
+            .into_iter()
+            .flat_map(|shard| shard.into_inner().unwrap())
+            .collect()
+    }
+
+    /// Reads every shard into a single map without draining them, so the
+    /// map can keep accumulating further increments afterwards.
+    pub fn snapshot(&self) -> HashMap<K, usize> {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.read().unwrap().clone())
+            .collect()
+    }
+}